@@ -0,0 +1,268 @@
+// Copyright (c) 2025 EdgeCoder, LLC
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Supervises the lifecycle of the Node agent process: spawning, health
+//! polling, crash-restart with backoff, and graceful shutdown.
+
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::provisioning;
+
+const AGENT_PORT: &str = "127.0.0.1:4301";
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(750);
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const STABLE_UPTIME_RESET: Duration = Duration::from_secs(60);
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+pub struct AgentProcess {
+    child: Mutex<Option<Child>>,
+    shutting_down: AtomicBool,
+}
+
+impl AgentProcess {
+    fn new(child: Option<Child>) -> Self {
+        Self {
+            child: Mutex::new(child),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+
+    /// PID of the currently supervised agent process, if one is running.
+    pub fn pid(&self) -> Option<u32> {
+        self.child.lock().ok()?.as_ref().map(Child::id)
+    }
+}
+
+fn agent_already_running() -> bool {
+    TcpStream::connect(AGENT_PORT).is_ok()
+}
+
+struct AgentLocation {
+    node_exe: PathBuf,
+    agent_dir: PathBuf,
+}
+
+/// Resolves the node executable and agent directory to launch. Prefers the
+/// provisioned runtime (see `provisioning`); falls back to a bundled
+/// resource or system install with `node` on PATH if provisioning hasn't
+/// run yet, so upgrades from older installs keep working. Called fresh on
+/// every (re)start attempt so a restart after `ensure_runtime` completes
+/// picks up the now-provisioned location instead of a stale one.
+fn resolve_location(app: &AppHandle) -> AgentLocation {
+    if let Some(provisioned) = provisioning::current(app) {
+        return AgentLocation {
+            node_exe: provisioned.node_exe,
+            agent_dir: provisioned.agent_dir,
+        };
+    }
+
+    let agent_dir = app
+        .path()
+        .resource_dir()
+        .ok()
+        .map(|p| p.join("agent"))
+        .filter(|p| p.join("dist/index.js").exists())
+        .unwrap_or_else(|| {
+            std::env::var("EDGECODER_INSTALL_DIR")
+                .unwrap_or_else(|_| "/opt/edgecoder/app".to_string())
+                .into()
+        });
+
+    AgentLocation {
+        node_exe: PathBuf::from("node"),
+        agent_dir,
+    }
+}
+
+fn spawn_agent(location: &AgentLocation, local_token: &str) -> Option<Child> {
+    if !location.agent_dir.join("dist/index.js").exists() {
+        tracing::warn!(agent_dir = ?location.agent_dir, "agent payload not found — skipping spawn");
+        return None;
+    }
+
+    let child = Command::new(&location.node_exe)
+        .arg("dist/index.js")
+        .current_dir(&location.agent_dir)
+        .env("EDGE_RUNTIME_MODE", "all-in-one")
+        .env("INFERENCE_AUTH_TOKEN", local_token)
+        .env("ADMIN_API_TOKEN", local_token)
+        .spawn();
+
+    match child {
+        Ok(child) => {
+            tracing::info!(pid = child.id(), "agent spawned");
+            Some(child)
+        }
+        Err(error) => {
+            tracing::error!(%error, "failed to spawn agent");
+            None
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send_terminate(child: &Child) {
+    // SAFETY: `child.id()` is a valid pid for a process we own.
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+    }
+}
+
+#[cfg(windows)]
+fn send_terminate(child: &Child) {
+    // `taskkill` without `/F` requests a graceful close rather than SIGKILL.
+    let _ = Command::new("taskkill")
+        .args(["/PID", &child.id().to_string(), "/T"])
+        .output();
+}
+
+fn wait_for_port_close(deadline: Instant) -> bool {
+    while Instant::now() < deadline {
+        if !agent_already_running() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    !agent_already_running()
+}
+
+/// Attempts a graceful shutdown (SIGTERM / taskkill, then wait for the port
+/// to close), escalating to a hard kill if the grace period elapses.
+fn graceful_shutdown(child: &mut Child, grace_period: Duration) {
+    send_terminate(child);
+    let deadline = Instant::now() + grace_period;
+    if !wait_for_port_close(deadline) {
+        tracing::warn!("agent did not exit within grace period — killing");
+        let _ = child.kill();
+    }
+    let _ = child.wait();
+}
+
+/// Spawns the agent and starts a background supervisor thread that restarts
+/// it with exponential backoff on unexpected exit. The thread stops once
+/// `shutdown` is called; `shutdown` itself terminates and reaps the child.
+pub fn spawn_supervised(app: &tauri::App, local_token: &str) -> Arc<AgentProcess> {
+    let handle = app.handle().clone();
+
+    let initial_child = if agent_already_running() {
+        tracing::warn!("agent already running on :4301 — skipping spawn");
+        None
+    } else {
+        let child = spawn_agent(&resolve_location(&handle), local_token);
+        if child.is_some() {
+            let _ = handle.emit("agent://up", ());
+        }
+        child
+    };
+
+    let process = Arc::new(AgentProcess::new(initial_child));
+    let supervised = process.clone();
+    let token = local_token.to_string();
+    // Only set once the agent has actually been spawned; `None` means the
+    // most recent start attempt did not succeed (or none was made), so the
+    // stable-uptime backoff reset below can't be tricked by time merely
+    // elapsing since thread start or since a failed restart.
+    let mut last_start = supervised.pid().map(|_| Instant::now());
+
+    thread::spawn(move || {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut restart_count = 0u32;
+
+        loop {
+            thread::sleep(HEALTH_POLL_INTERVAL);
+
+            if supervised.shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+
+            // A child that exited, or one that was never successfully
+            // spawned in the first place (e.g. the agent payload wasn't
+            // provisioned yet), both need a (re)start attempt — unless the
+            // port is already held by an agent we don't own (the original
+            // "already running elsewhere" skip at startup), in which case
+            // there's nothing for us to restart.
+            let needs_restart = {
+                let mut guard = match supervised.child.lock() {
+                    Ok(g) => g,
+                    Err(_) => return,
+                };
+                match guard.as_mut() {
+                    Some(child) => matches!(child.try_wait(), Ok(Some(_))),
+                    None => !agent_already_running(),
+                }
+            };
+
+            if !needs_restart {
+                continue;
+            }
+
+            if supervised.shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let _ = handle.emit("agent://down", ());
+
+            if last_start.is_some_and(|start| start.elapsed() >= STABLE_UPTIME_RESET) {
+                restart_count = 0;
+                backoff = INITIAL_BACKOFF;
+            }
+            restart_count += 1;
+
+            tracing::warn!(restart_count, backoff_ms = backoff.as_millis() as u64, "agent down — restarting");
+            let _ = handle.emit("agent://restarting", backoff.as_millis() as u64);
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+
+            if supervised.shutting_down.load(Ordering::SeqCst) {
+                return;
+            }
+
+            // Re-resolve on every attempt: provisioning may have completed
+            // since the last try, and the provisioned location can change.
+            let location = resolve_location(&handle);
+            match spawn_agent(&location, &token) {
+                Some(child) => {
+                    last_start = Some(Instant::now());
+                    let pid = child.id();
+                    if let Ok(mut guard) = supervised.child.lock() {
+                        *guard = Some(child);
+                    }
+                    tracing::info!(pid, restart_count, "agent restarted");
+                    let _ = handle.emit("agent://up", ());
+                }
+                None => {
+                    last_start = None;
+                    tracing::error!(restart_count, "agent restart failed — will retry after next backoff");
+                }
+            }
+        }
+    });
+
+    process
+}
+
+/// Shuts the agent down gracefully; called on window destroy.
+pub fn shutdown(process: &AgentProcess) {
+    process.shutting_down.store(true, Ordering::SeqCst);
+    if let Ok(mut guard) = process.child.lock() {
+        if let Some(child) = guard.as_mut() {
+            graceful_shutdown(child, SHUTDOWN_GRACE_PERIOD);
+        }
+        *guard = None;
+    }
+}
+
+pub fn on_window_destroyed(app: &AppHandle) {
+    if let Some(state) = app.try_state::<Arc<AgentProcess>>() {
+        shutdown(&state);
+    }
+}
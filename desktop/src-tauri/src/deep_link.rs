@@ -0,0 +1,192 @@
+// Copyright (c) 2025 EdgeCoder, LLC
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Deep-link ingestion: validates inbound `edgecoder://` URLs, enforces
+//! CSRF state validation on OAuth callbacks, and queues callbacks that
+//! arrive before the main webview signals it's ready.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use tauri::{AppHandle, Emitter, Manager};
+use url::Url;
+
+const EXPECTED_SCHEME: &str = "edgecoder";
+const OAUTH_CALLBACK_HOST: &str = "oauth-callback";
+const OAUTH_CALLBACK_PATHS: &[&str] = &["/", ""];
+
+/// Hosts the renderer knows how to handle. Anything else is rejected
+/// before it ever reaches `window.__handleDeepLink`.
+const ALLOWED_HOSTS: &[&str] = &[OAUTH_CALLBACK_HOST, "open"];
+
+/// Constant-time byte comparison for the CSRF state check below, so a
+/// mismatched callback can't be distinguished by timing a `==` short-circuit.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[derive(Default)]
+pub struct DeepLinkManager {
+    pending_oauth_state: Mutex<Option<String>>,
+    queue: Mutex<Vec<String>>,
+    ready: AtomicBool,
+}
+
+impl DeepLinkManager {
+    /// Generates a CSRF state value for an outbound OAuth request and
+    /// remembers it so the callback can be validated against it.
+    pub fn begin_oauth(&self) -> String {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let state = hex::encode(bytes);
+        *self
+            .pending_oauth_state
+            .lock()
+            .expect("oauth state mutex poisoned") = Some(state.clone());
+        state
+    }
+
+    fn consume_if_valid_state(&self, candidate: &str) -> bool {
+        let mut guard = self
+            .pending_oauth_state
+            .lock()
+            .expect("oauth state mutex poisoned");
+        match guard.take() {
+            Some(expected) => constant_time_eq(expected.as_bytes(), candidate.as_bytes()),
+            None => false,
+        }
+    }
+
+    fn validate(&self, url: &Url) -> bool {
+        if url.scheme() != EXPECTED_SCHEME {
+            tracing::warn!(scheme = url.scheme(), "rejecting deep link with unexpected scheme");
+            return false;
+        }
+
+        let host = url.host_str();
+        if !host.is_some_and(|h| ALLOWED_HOSTS.contains(&h)) {
+            tracing::warn!(host = ?host, %url, "rejecting deep link with unrecognized host");
+            return false;
+        }
+
+        if host == Some(OAUTH_CALLBACK_HOST) {
+            if !OAUTH_CALLBACK_PATHS.contains(&url.path()) {
+                tracing::warn!(path = url.path(), %url, "rejecting oauth callback with unexpected path");
+                return false;
+            }
+
+            let state = url
+                .query_pairs()
+                .find(|(k, _)| k == "state")
+                .map(|(_, v)| v.into_owned());
+            if !state.is_some_and(|s| self.consume_if_valid_state(&s)) {
+                tracing::warn!(%url, "rejecting oauth callback with missing/invalid CSRF state");
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Validates and dispatches (or queues) every URL from one deep-link
+    /// event, rather than only looking at the first.
+    pub fn handle_urls(&self, app: &AppHandle, urls: Vec<Url>) {
+        for url in urls {
+            if !self.validate(&url) {
+                continue;
+            }
+
+            let url_str = url.to_string();
+            if self.ready.load(Ordering::SeqCst) {
+                dispatch(app, &url_str);
+            } else {
+                tracing::info!(url = %url_str, "queuing deep link received before webview ready");
+                self.queue
+                    .lock()
+                    .expect("deep link queue poisoned")
+                    .push(url_str);
+            }
+        }
+    }
+
+    /// Marks the main webview ready and flushes any queued callbacks.
+    pub fn mark_ready(&self, app: &AppHandle) {
+        self.ready.store(true, Ordering::SeqCst);
+        let queued = std::mem::take(&mut *self.queue.lock().expect("deep link queue poisoned"));
+        for url_str in queued {
+            dispatch(app, &url_str);
+        }
+    }
+}
+
+fn dispatch(app: &AppHandle, url_str: &str) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.eval(&format!(
+            "window.__handleDeepLink({})",
+            serde_json::to_string(url_str).unwrap_or_default()
+        ));
+    }
+}
+
+#[tauri::command]
+pub fn begin_oauth(manager: tauri::State<'_, Arc<DeepLinkManager>>) -> String {
+    manager.begin_oauth()
+}
+
+#[tauri::command]
+pub fn ack_deep_link_ready(app: AppHandle, manager: tauri::State<'_, Arc<DeepLinkManager>>) {
+    manager.mark_ready(&app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn callback_url(state: &str) -> Url {
+        Url::parse(&format!("edgecoder://oauth-callback/?state={state}")).unwrap()
+    }
+
+    #[test]
+    fn valid_state_is_accepted() {
+        let manager = DeepLinkManager::default();
+        let state = manager.begin_oauth();
+        assert!(manager.validate(&callback_url(&state)));
+    }
+
+    #[test]
+    fn mismatched_state_is_rejected() {
+        let manager = DeepLinkManager::default();
+        manager.begin_oauth();
+        assert!(!manager.validate(&callback_url("not-the-right-state")));
+    }
+
+    #[test]
+    fn replayed_state_is_rejected() {
+        let manager = DeepLinkManager::default();
+        let state = manager.begin_oauth();
+        assert!(manager.validate(&callback_url(&state)));
+        // `consume_if_valid_state` takes the pending state, so a second
+        // callback with the same value must not validate again.
+        assert!(!manager.validate(&callback_url(&state)));
+    }
+
+    #[test]
+    fn disallowed_host_is_rejected() {
+        let manager = DeepLinkManager::default();
+        let url = Url::parse("edgecoder://not-a-known-host/").unwrap();
+        assert!(!manager.validate(&url));
+    }
+
+    #[test]
+    fn unexpected_oauth_callback_path_is_rejected() {
+        let manager = DeepLinkManager::default();
+        let state = manager.begin_oauth();
+        let url = Url::parse(&format!("edgecoder://oauth-callback/unexpected-path?state={state}")).unwrap();
+        assert!(!manager.validate(&url));
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2025 EdgeCoder, LLC
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Structured tracing setup: events go to stderr and to a size- and
+//! date-rotating file under the platform log directory, honoring
+//! `EDGECODER_LOG` as an `EnvFilter`. Also bridges events into an
+//! in-memory ring buffer and a `logs://line` event so the renderer can
+//! show a live diagnostics panel.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use rolling_file::{BasicRollingFileAppender, RollingConditionBasic};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+const RING_BUFFER_CAPACITY: usize = 2000;
+const DEFAULT_FILTER: &str = "info";
+const MAX_LOG_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_ROTATED_LOG_FILES: usize = 14;
+
+#[derive(Serialize, Clone)]
+pub struct LogLine {
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub timestamp_ms: u128,
+    /// Every structured field on the event other than `message` (e.g. the
+    /// agent's `pid`/`restart_count`/`backoff_ms`, a deep link's
+    /// `scheme`/`host`/`path`), so the in-app diagnostics panel gets the
+    /// same detail as the file/stderr logs.
+    pub fields: BTreeMap<String, String>,
+}
+
+pub struct LogBuffer {
+    lines: Mutex<VecDeque<LogLine>>,
+}
+
+impl LogBuffer {
+    fn push(&self, line: LogLine) {
+        let mut lines = self.lines.lock().expect("log buffer mutex poisoned");
+        if lines.len() >= RING_BUFFER_CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Most recent `limit` lines, optionally filtered by level, oldest first.
+    pub fn recent(&self, level: Option<&str>, limit: usize) -> Vec<LogLine> {
+        let lines = self.lines.lock().expect("log buffer mutex poisoned");
+        let mut matched: Vec<LogLine> = lines
+            .iter()
+            .rev()
+            .filter(|l| level.map_or(true, |lvl| l.level.eq_ignore_ascii_case(lvl)))
+            .take(limit)
+            .cloned()
+            .collect();
+        matched.reverse();
+        matched
+    }
+}
+
+#[derive(Default)]
+struct EventFieldsVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl tracing::field::Visit for EventFieldsVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            self.fields.insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+}
+
+/// Bridges every tracing event into the ring buffer and a `logs://line`
+/// event for the renderer, independent of the stderr/file writers.
+struct EventBridgeLayer {
+    app: AppHandle,
+    buffer: Arc<LogBuffer>,
+}
+
+impl<S: tracing::Subscriber> Layer<S> for EventBridgeLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = EventFieldsVisitor::default();
+        event.record(&mut visitor);
+
+        let timestamp_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let line = LogLine {
+            level: event.metadata().level().to_string(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+            timestamp_ms,
+            fields: visitor.fields,
+        };
+
+        self.buffer.push(line.clone());
+        let _ = self.app.emit("logs://line", &line);
+    }
+}
+
+/// Opens the rotating file appender, falling back to a temp dir if the
+/// platform log dir can't be used. File logging is a diagnostics nicety,
+/// not something that should be able to keep the app from launching, so
+/// this never panics — `None` means stderr-only logging.
+fn open_file_appender(log_dir: &std::path::Path) -> Option<BasicRollingFileAppender> {
+    // Rotate on whichever comes first: a new day, or MAX_LOG_FILE_BYTES —
+    // `tracing_appender::rolling` only covers the date axis, which leaves a
+    // single noisy day able to grow the file unbounded.
+    let rolling_condition = || RollingConditionBasic::new().daily().max_size(MAX_LOG_FILE_BYTES);
+
+    BasicRollingFileAppender::new(log_dir.join("edgecoder.log"), rolling_condition(), MAX_ROTATED_LOG_FILES)
+        .or_else(|error| {
+            eprintln!("failed to open rotating log file at {log_dir:?}: {error}");
+            let fallback_dir = std::env::temp_dir();
+            BasicRollingFileAppender::new(fallback_dir.join("edgecoder.log"), rolling_condition(), MAX_ROTATED_LOG_FILES)
+        })
+        .map_err(|error| eprintln!("failed to open fallback log file: {error} — logging to stderr only"))
+        .ok()
+}
+
+/// Installs the global tracing subscriber. The returned `WorkerGuard`, if
+/// any, must be held for the lifetime of the process to flush the file
+/// writer; it's `None` when file logging couldn't be set up at all.
+pub fn init(app: &AppHandle) -> (Option<WorkerGuard>, Arc<LogBuffer>) {
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .unwrap_or_else(|_| std::env::temp_dir());
+    let _ = std::fs::create_dir_all(&log_dir);
+
+    let filter = std::env::var("EDGECODER_LOG")
+        .ok()
+        .and_then(|v| EnvFilter::try_new(v).ok())
+        .unwrap_or_else(|| EnvFilter::new(DEFAULT_FILTER));
+
+    let buffer = Arc::new(LogBuffer {
+        lines: Mutex::new(VecDeque::with_capacity(RING_BUFFER_CAPACITY)),
+    });
+
+    let (file_layer, guard) = match open_file_appender(&log_dir) {
+        Some(file_appender) => {
+            let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+            let layer = tracing_subscriber::fmt::layer()
+                .with_writer(non_blocking)
+                .with_ansi(false);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    let subscriber = Registry::default()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(file_layer)
+        .with(EventBridgeLayer {
+            app: app.clone(),
+            buffer: buffer.clone(),
+        });
+
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("failed to install global tracing subscriber");
+
+    (guard, buffer)
+}
+
+#[tauri::command]
+pub fn get_recent_logs(
+    state: tauri::State<'_, Arc<LogBuffer>>,
+    level: Option<String>,
+    limit: Option<usize>,
+) -> Vec<LogLine> {
+    state.recent(level.as_deref(), limit.unwrap_or(200))
+}
@@ -0,0 +1,180 @@
+// Copyright (c) 2025 EdgeCoder, LLC
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Long-lived system metrics sampler. A background thread refreshes a
+//! single `System` on a fixed cadence (sysinfo needs two refreshes spaced by
+//! `MINIMUM_CPU_UPDATE_INTERVAL` before CPU usage is meaningful) and emits
+//! `metrics://update` events; `get_system_metrics` just reads the latest
+//! cached snapshot.
+
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sysinfo::{Networks, Pid, System};
+use tauri::{AppHandle, Emitter};
+
+use crate::agent::AgentProcess;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(1000);
+// `nvidia-smi` is a subprocess spawn, not a syscall — sample it far less
+// often than the rest of the snapshot so it doesn't fork once a second
+// forever (the same "hammering the syscall layer" bug this sampler exists
+// to fix, just reintroduced for GPU).
+const GPU_SAMPLE_EVERY_N_TICKS: u32 = 10;
+
+#[derive(Serialize, Clone, Default)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct AgentProcessMetrics {
+    pub pid: u32,
+    pub cpu_usage_percent: f32,
+    pub memory_rss_mb: u64,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct SystemMetrics {
+    pub cpu_usage_percent: f32,
+    pub per_core_usage_percent: Vec<f32>,
+    pub load_average: LoadAverage,
+    pub memory_used_mb: u64,
+    pub memory_total_mb: u64,
+    pub disk_used_gb: f64,
+    pub disk_total_gb: f64,
+    pub network_rx_bytes_per_sec: u64,
+    pub network_tx_bytes_per_sec: u64,
+    pub agent_process: Option<AgentProcessMetrics>,
+    pub gpu_utilization_percent: Option<f32>,
+}
+
+pub struct MetricsState {
+    latest: Mutex<SystemMetrics>,
+}
+
+impl MetricsState {
+    pub fn snapshot(&self) -> SystemMetrics {
+        self.latest.lock().expect("metrics mutex poisoned").clone()
+    }
+}
+
+/// Best-effort GPU utilization via `nvidia-smi`; `None` when unavailable.
+fn sample_gpu_utilization() -> Option<f32> {
+    let output = Command::new("nvidia-smi")
+        .args(["--query-gpu=utilization.gpu", "--format=csv,noheader,nounits"])
+        .output()
+        .ok()?;
+    String::from_utf8(output.stdout)
+        .ok()?
+        .lines()
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn sample_agent_process(sys: &System, agent: &AgentProcess) -> Option<AgentProcessMetrics> {
+    let pid = agent.pid()?;
+    let process = sys.process(Pid::from_u32(pid))?;
+    Some(AgentProcessMetrics {
+        pid,
+        cpu_usage_percent: process.cpu_usage(),
+        memory_rss_mb: process.memory() / 1_048_576,
+    })
+}
+
+/// Starts the background sampler thread and returns the shared state that
+/// `get_system_metrics` reads from.
+pub fn spawn_sampler(app: AppHandle, agent: Arc<AgentProcess>) -> Arc<MetricsState> {
+    let state = Arc::new(MetricsState {
+        latest: Mutex::new(SystemMetrics::default()),
+    });
+    let sampler_state = state.clone();
+
+    thread::spawn(move || {
+        let mut sys = System::new_all();
+        let mut networks = Networks::new_with_refreshed_list();
+        let mut last_network_refresh = Instant::now();
+        sys.refresh_all();
+        // First CPU reading is meaningless until a second refresh has
+        // elapsed at least MINIMUM_CPU_UPDATE_INTERVAL after the first.
+        thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+
+        let mut tick: u32 = 0;
+        let mut gpu_utilization_percent: Option<f32> = None;
+
+        loop {
+            sys.refresh_cpu_usage();
+            sys.refresh_memory();
+            sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+            networks.refresh();
+            let network_interval_secs = last_network_refresh.elapsed().as_secs_f64().max(f64::EPSILON);
+            last_network_refresh = Instant::now();
+
+            let cpus = sys.cpus();
+            let per_core_usage_percent: Vec<f32> = cpus.iter().map(|c| c.cpu_usage()).collect();
+            let cpu_usage_percent = if !per_core_usage_percent.is_empty() {
+                per_core_usage_percent.iter().sum::<f32>() / per_core_usage_percent.len() as f32
+            } else {
+                0.0
+            };
+
+            let load = System::load_average();
+            let (rx, tx) = networks
+                .iter()
+                .fold((0u64, 0u64), |(rx, tx), (_, data)| {
+                    (rx + data.received(), tx + data.transmitted())
+                });
+
+            let disks = sysinfo::Disks::new_with_refreshed_list();
+            let disk_total_bytes: u64 = disks.iter().map(|d| d.total_space()).sum();
+            let disk_available_bytes: u64 = disks.iter().map(|d| d.available_space()).sum();
+            let disk_used_bytes = disk_total_bytes.saturating_sub(disk_available_bytes);
+
+            if tick % GPU_SAMPLE_EVERY_N_TICKS == 0 {
+                gpu_utilization_percent = sample_gpu_utilization();
+            }
+            tick = tick.wrapping_add(1);
+
+            let metrics = SystemMetrics {
+                cpu_usage_percent,
+                per_core_usage_percent,
+                load_average: LoadAverage {
+                    one: load.one,
+                    five: load.five,
+                    fifteen: load.fifteen,
+                },
+                memory_used_mb: sys.used_memory() / 1_048_576,
+                memory_total_mb: sys.total_memory() / 1_048_576,
+                disk_used_gb: disk_used_bytes as f64 / 1_073_741_824.0,
+                disk_total_gb: disk_total_bytes as f64 / 1_073_741_824.0,
+                // `networks.refresh()` reports deltas since the previous call;
+                // scale them by the time that actually elapsed since then.
+                network_rx_bytes_per_sec: (rx as f64 / network_interval_secs) as u64,
+                network_tx_bytes_per_sec: (tx as f64 / network_interval_secs) as u64,
+                agent_process: sample_agent_process(&sys, &agent),
+                gpu_utilization_percent,
+            };
+
+            tracing::debug!(
+                cpu_usage_percent = metrics.cpu_usage_percent,
+                memory_used_mb = metrics.memory_used_mb,
+                agent_pid = metrics.agent_process.as_ref().map(|p| p.pid),
+                "sampled system metrics"
+            );
+
+            *sampler_state.latest.lock().expect("metrics mutex poisoned") = metrics.clone();
+            let _ = app.emit("metrics://update", &metrics);
+
+            thread::sleep(SAMPLE_INTERVAL);
+        }
+    });
+
+    state
+}
@@ -0,0 +1,227 @@
+// Copyright (c) 2025 EdgeCoder, LLC
+// SPDX-License-Identifier: BUSL-1.1
+
+//! First-run provisioning of the Node runtime and agent payload: downloads
+//! the platform/arch artifact, verifies it against an embedded SHA-256
+//! manifest, and unpacks it atomically into the app data dir. Idempotent
+//! once a verified install is in place.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Manager};
+
+struct Artifact {
+    os: &'static str,
+    arch: &'static str,
+    url: &'static str,
+    sha256: &'static str,
+}
+
+/// Embedded manifest of known-good runtime/agent bundles per platform.
+const MANIFEST: &[Artifact] = &[
+    Artifact {
+        os: "macos",
+        arch: "aarch64",
+        url: "https://dl.edgecoder.dev/runtime/darwin-arm64.tar.gz",
+        sha256: "0000000000000000000000000000000000000000000000000000000000000000",
+    },
+    Artifact {
+        os: "macos",
+        arch: "x86_64",
+        url: "https://dl.edgecoder.dev/runtime/darwin-x64.tar.gz",
+        sha256: "0000000000000000000000000000000000000000000000000000000000000000",
+    },
+    Artifact {
+        os: "linux",
+        arch: "x86_64",
+        url: "https://dl.edgecoder.dev/runtime/linux-x64.tar.gz",
+        sha256: "0000000000000000000000000000000000000000000000000000000000000000",
+    },
+    Artifact {
+        os: "windows",
+        arch: "x86_64",
+        url: "https://dl.edgecoder.dev/runtime/win32-x64.zip",
+        sha256: "0000000000000000000000000000000000000000000000000000000000000000",
+    },
+];
+
+#[derive(Serialize, Clone)]
+struct ProvisionProgress {
+    phase: &'static str,
+    bytes_downloaded: u64,
+    total_bytes: u64,
+}
+
+pub struct ProvisionedRuntime {
+    pub node_exe: PathBuf,
+    pub agent_dir: PathBuf,
+}
+
+/// Serializes `ensure_runtime` invocations so two concurrent calls (a
+/// frontend effect firing twice, a user retrying mid-flight) can't both
+/// race on the same `download.tmp` / staging-then-rename sequence.
+#[derive(Default)]
+pub struct ProvisioningLock(tokio::sync::Mutex<()>);
+
+fn current_artifact() -> Option<&'static Artifact> {
+    MANIFEST
+        .iter()
+        .find(|a| a.os == std::env::consts::OS && a.arch == std::env::consts::ARCH)
+}
+
+fn runtime_root(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join("runtime")
+}
+
+fn verified_marker(root: &Path) -> PathBuf {
+    root.join(".verified")
+}
+
+fn node_exe_path(live: &Path) -> PathBuf {
+    if cfg!(windows) {
+        live.join("node/node.exe")
+    } else {
+        live.join("node/bin/node")
+    }
+}
+
+/// Returns the provisioned runtime location if a verified install already
+/// exists, without touching the network.
+pub fn current(app: &AppHandle) -> Option<ProvisionedRuntime> {
+    let root = runtime_root(app);
+    if !verified_marker(&root).exists() {
+        return None;
+    }
+    let live = root.join("current");
+    Some(ProvisionedRuntime {
+        node_exe: node_exe_path(&live),
+        agent_dir: live.join("agent"),
+    })
+}
+
+fn sha256_hex(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 65536];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Downloads `url` to `dest`, emitting `runtime://progress` as bytes arrive.
+///
+/// Uses the async `reqwest::Client` rather than `reqwest::blocking`: this
+/// command runs on Tauri's tokio runtime, and the blocking client panics
+/// ("cannot start a runtime from within a runtime") if invoked there.
+async fn download_with_progress(app: &AppHandle, url: &str, dest: &Path) -> Result<(), String> {
+    let mut response = reqwest::get(url)
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?;
+    let total_bytes = response.content_length().unwrap_or(0);
+    let mut file = std::fs::File::create(dest).map_err(|e| e.to_string())?;
+    let mut bytes_downloaded = 0u64;
+
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        bytes_downloaded += chunk.len() as u64;
+        let _ = app.emit(
+            "runtime://progress",
+            ProvisionProgress {
+                phase: "downloading",
+                bytes_downloaded,
+                total_bytes,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+fn unpack_atomically(archive: &Path, root: &Path) -> Result<(), String> {
+    let staging = root.join(".staging");
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).map_err(|e| e.to_string())?;
+    }
+    std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+
+    let file = std::fs::File::open(archive).map_err(|e| e.to_string())?;
+    let decompressed = flate2::read::GzDecoder::new(file);
+    let mut archive = tar::Archive::new(decompressed);
+    archive.unpack(&staging).map_err(|e| e.to_string())?;
+
+    // Atomically swap the staged extraction into place.
+    let live = root.join("current");
+    if live.exists() {
+        std::fs::remove_dir_all(&live).map_err(|e| e.to_string())?;
+    }
+    std::fs::rename(&staging, &live).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Downloads, verifies, and unpacks the runtime/agent bundle for this
+/// platform if it isn't already provisioned. No-op once verified.
+#[tauri::command]
+pub async fn ensure_runtime(app: AppHandle, lock: tauri::State<'_, ProvisioningLock>) -> Result<(), String> {
+    // Hold the lock across the whole download/verify/unpack sequence so a
+    // second concurrent call blocks here instead of racing on
+    // `download.tmp` or `unpack_atomically`'s staging/current swap, then
+    // finds the first call's result already verified below.
+    let _guard = lock.0.lock().await;
+
+    if current(&app).is_some() {
+        return Ok(());
+    }
+
+    let artifact = current_artifact()
+        .ok_or_else(|| format!("no runtime artifact for {}-{}", std::env::consts::OS, std::env::consts::ARCH))?;
+
+    let root = runtime_root(&app);
+    std::fs::create_dir_all(&root).map_err(|e| e.to_string())?;
+    let download_path = root.join("download.tmp");
+
+    let _ = app.emit(
+        "runtime://progress",
+        ProvisionProgress { phase: "downloading", bytes_downloaded: 0, total_bytes: 0 },
+    );
+    download_with_progress(&app, artifact.url, &download_path).await?;
+
+    let _ = app.emit(
+        "runtime://progress",
+        ProvisionProgress { phase: "verifying", bytes_downloaded: 0, total_bytes: 0 },
+    );
+    let digest = sha256_hex(&download_path).map_err(|e| e.to_string())?;
+    if digest != artifact.sha256 {
+        let _ = std::fs::remove_file(&download_path);
+        return Err(format!(
+            "checksum mismatch for runtime artifact: expected {}, got {digest}",
+            artifact.sha256
+        ));
+    }
+
+    let _ = app.emit(
+        "runtime://progress",
+        ProvisionProgress { phase: "unpacking", bytes_downloaded: 0, total_bytes: 0 },
+    );
+    unpack_atomically(&download_path, &root)?;
+    let _ = std::fs::remove_file(&download_path);
+    std::fs::write(verified_marker(&root), digest).map_err(|e| e.to_string())?;
+
+    let _ = app.emit(
+        "runtime://progress",
+        ProvisionProgress { phase: "complete", bytes_downloaded: 0, total_bytes: 0 },
+    );
+    Ok(())
+}
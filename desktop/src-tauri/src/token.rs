@@ -0,0 +1,43 @@
+// Copyright (c) 2025 EdgeCoder, LLC
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Generates and persists the local inference/admin auth token using the
+//! platform credential store, so it survives restarts without ever being
+//! derived from observable process state.
+
+use keyring::Entry;
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+const KEYRING_SERVICE: &str = "com.edgecoder.app";
+const KEYRING_ACCOUNT: &str = "local-token";
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Loads the persisted local token, generating and storing a fresh one if
+/// none can be read from the credential store. The entry uses a fixed
+/// service/account pair, so every install under the same OS user shares it.
+pub fn load_or_create() -> String {
+    let entry = match Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT) {
+        Ok(entry) => entry,
+        Err(e) => {
+            eprintln!("Keyring unavailable ({e}) — using in-memory token");
+            return generate_token();
+        }
+    };
+
+    match entry.get_password() {
+        Ok(token) => token,
+        Err(_) => {
+            let token = generate_token();
+            if let Err(e) = entry.set_password(&token) {
+                eprintln!("Failed to persist local token to keyring: {e}");
+            }
+            token
+        }
+    }
+}